@@ -0,0 +1,12 @@
+//! A library to deal with Proguard mapping files.
+//!
+//! This allows parsing Proguard mapping files, and using them to remap
+//! classes, stack frames, or complete stacktraces.
+
+mod mapper;
+mod mapping;
+mod stacktrace;
+
+pub use mapper::{ProguardMapper, RemappedFrameIter, SourceProvider, StackFrameWithContext};
+pub use mapping::{LineMapping, ParseError, ProguardMapping, ProguardRecord};
+pub use stacktrace::StackFrame;
@@ -1,10 +1,98 @@
+use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
 use std::iter::FusedIterator;
+use std::rc::Rc;
+
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
 
 use crate::mapping::{ProguardMapping, ProguardRecord};
 use crate::stacktrace::StackFrame;
 
+/// Parses a `pg_map_hash` header value, e.g. `SHA-256 613ff...`.
+fn parse_map_hash(value: &str) -> Option<[u8; 32]> {
+    let (algorithm, hex) = value.split_once(' ')?;
+    if !algorithm.eq_ignore_ascii_case("SHA-256") || hex.len() != 64 {
+        return None;
+    }
+    let mut hash = [0u8; 32];
+    for (byte, chunk) in hash.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+    }
+    Some(hash)
+}
+
+/// Number of lines of source kept on either side of the mapped line.
+const CONTEXT_LINES: usize = 5;
+
+/// Prefixes of stacktrace lines that, other than `at ...` frames, also carry
+/// an obfuscated, fully-qualified throwable class name.
+const THROWABLE_LINE_PREFIXES: &[&str] = &["Caused by: ", "Suppressed: "];
+
+/// A source of file contents, used to resolve [`StackFrameWithContext`]s.
+///
+/// Implementors are free to fetch sources from disk, a debug-file store, or
+/// any other backend. `remap_frame_with_context` calls [`get_source`] at
+/// most once per distinct file name: the decoded lines of a successfully
+/// resolved file are cached for later frames, and a file that turned out to
+/// be unusable (missing, hash mismatch, invalid UTF-8) is remembered too, so
+/// it is never retried.
+///
+/// [`get_source`]: SourceProvider::get_source
+pub trait SourceProvider {
+    /// Returns the raw bytes of `file`, or `None` if it is unavailable.
+    fn get_source(&mut self, file: &str) -> Option<Vec<u8>>;
+
+    /// Returns the expected SHA-256 hash of `file`, if the caller has one on
+    /// record.
+    ///
+    /// When present, the hash is compared against the bytes returned from
+    /// [`get_source`] before any context is sliced out, guarding against
+    /// sources that have drifted from the build that produced the mapping.
+    ///
+    /// [`get_source`]: SourceProvider::get_source
+    fn expected_hash(&self, _file: &str) -> Option<[u8; 32]> {
+        None
+    }
+}
+
+/// A [`StackFrame`] together with the source lines surrounding it.
+///
+/// Returned by [`ProguardMapper::remap_frame_with_context`].
+#[derive(Clone, Debug)]
+pub struct StackFrameWithContext<'s> {
+    /// The remapped stack frame.
+    pub frame: StackFrame<'s>,
+    /// Source lines directly preceding [`context_line`](Self::context_line).
+    pub pre_context: Vec<String>,
+    /// The source line the frame points at, if it could be resolved.
+    pub context_line: Option<String>,
+    /// Source lines directly following [`context_line`](Self::context_line).
+    pub post_context: Vec<String>,
+}
+
+impl<'s> From<StackFrame<'s>> for StackFrameWithContext<'s> {
+    fn from(frame: StackFrame<'s>) -> Self {
+        Self {
+            frame,
+            pre_context: Vec::new(),
+            context_line: None,
+            post_context: Vec::new(),
+        }
+    }
+}
+
+/// The outcome of resolving a single file through a [`SourceProvider`],
+/// cached so later frames pointing at the same file skip the fetch/hash.
+#[derive(Clone, Debug)]
+enum CachedSource {
+    /// The file was fetched, hash-verified (if applicable), and decoded.
+    Lines(Rc<Vec<String>>),
+    /// The file is missing, failed hash verification, or wasn't valid UTF-8.
+    Unusable,
+}
+
 #[derive(Clone, Debug)]
 struct MemberMapping<'s> {
     startline: usize,
@@ -44,20 +132,30 @@ impl<'m> RemappedFrameIter<'m> {
 impl<'m> Iterator for RemappedFrameIter<'m> {
     type Item = StackFrame<'m>;
     fn next(&mut self) -> Option<Self::Item> {
-        let (frame, ref mut members) = self.inner.as_mut()?;
+        // frames without a line (native/synthetic frames) can’t be narrowed
+        // down to a specific member by range, so an obfuscated name that
+        // covers several distinct overloads would otherwise match all of
+        // them; take the iterator so at most one candidate is ever yielded.
+        let (frame, mut members) = self.inner.take()?;
 
-        for member in members {
+        for member in &mut members {
             // skip any members which do not match our the frames line
-            if member.endline > 0 && (frame.line < member.startline || frame.line > member.endline)
-            {
-                continue;
+            if let Some(frame_line) = frame.line {
+                if member.endline > 0
+                    && (frame_line < member.startline || frame_line > member.endline)
+                {
+                    continue;
+                }
             }
             // parents of inlined frames don’t have an `endline`, and
-            // the top inlined frame need to be correctly offset.
-            let line = if member.original_endline.is_none() {
-                member.original_startline
-            } else {
-                member.original_startline + frame.line - member.startline
+            // the top inlined frame need to be correctly offset. frames
+            // without a line number can’t be offset into the original range.
+            let line = match (frame.line, member.original_endline) {
+                (None, _) => None,
+                (Some(_), None) => Some(member.original_startline),
+                (Some(frame_line), Some(_)) => {
+                    Some(member.original_startline + frame_line - member.startline)
+                }
             };
             // when an inlined function is from a foreign class, we
             // don’t know the file it is defined in.
@@ -70,12 +168,21 @@ impl<'m> Iterator for RemappedFrameIter<'m> {
                 Some(class) => class.into(),
                 _ => frame.class.clone(),
             };
-            return Some(StackFrame {
+            let result = StackFrame {
                 class,
                 method: member.original.into(),
                 file,
                 line,
-            });
+            };
+
+            // a frame with a line number may still have further inlined
+            // members to yield on the next call; one without a line has no
+            // way to disambiguate further matches, so we leave `self.inner`
+            // cleared instead of putting `(frame, members)` back.
+            if frame.line.is_some() {
+                self.inner = Some((frame, members));
+            }
+            return Some(result);
         }
 
         None
@@ -91,6 +198,24 @@ impl FusedIterator for RemappedFrameIter<'_> {}
 #[derive(Clone, Debug)]
 pub struct ProguardMapper<'s> {
     classes: HashMap<&'s str, ClassMapping<'s>>,
+    /// Per-file source lookups already resolved via [`SourceProvider`],
+    /// keyed by file name, so each file is only ever fetched once, whether
+    /// it resolved successfully or turned out to be unusable.
+    source_cache: RefCell<HashMap<String, CachedSource>>,
+    /// The raw mapping source, used by [`verify`](Self::verify) to recompute
+    /// the declared `pg_map_hash`.
+    source: &'s str,
+    /// Byte offset of the mapping body, i.e. everything past the leading
+    /// `#`-comment header lines.
+    body_offset: usize,
+    map_id: Option<&'s str>,
+    /// The parsed `pg_map_hash` digest, if the header declared one and it
+    /// parsed successfully.
+    map_hash: Option<[u8; 32]>,
+    /// Whether the header declared a `pg_map_hash` at all, independent of
+    /// whether [`map_hash`](Self::map_hash) parsed. A declared-but-malformed
+    /// hash must fail [`verify`](Self::verify), not be treated as absent.
+    has_map_hash: bool,
 }
 
 impl<'s> From<&'s str> for ProguardMapper<'s> {
@@ -109,9 +234,42 @@ impl<'s> ProguardMapper<'s> {
             obfuscated: "",
             members: BTreeMap::new(),
         };
+        let mut map_id = None;
+        let mut map_hash = None;
+        let mut has_map_hash = false;
+        let mut body_offset = 0;
+        let mut in_header = true;
 
-        for record in mapping.iter().filter_map(Result::ok) {
+        // `split_inclusive` keeps each line's original terminator (`\n` or
+        // `\r\n`) attached, so summing `raw_line.len()` gives the true byte
+        // offset regardless of line-ending style; `.lines().len() + 1`
+        // assumes a bare `\n` and undercounts CRLF files by one byte per line.
+        for (result, raw_line) in mapping.iter().zip(mapping.source().split_inclusive('\n')) {
+            // only the leading run of header-comment lines declares
+            // `pg_map_id`/`pg_map_hash`; a `# ...` comment further down the
+            // file (R8 emits plenty of those) must not override them.
+            let is_header_line = in_header && matches!(result, Ok(ProguardRecord::Header { .. }));
+            if in_header {
+                if is_header_line {
+                    body_offset += raw_line.len();
+                } else {
+                    in_header = false;
+                }
+            }
+            let record = match result {
+                Ok(record) => record,
+                Err(_) => continue,
+            };
             match record {
+                ProguardRecord::Header { key, value } if is_header_line => match key {
+                    "pg_map_id" => map_id = value,
+                    "pg_map_hash" => {
+                        has_map_hash = true;
+                        map_hash = value.and_then(parse_map_hash);
+                    }
+                    _ => {}
+                },
+                ProguardRecord::Header { .. } => {}
                 ProguardRecord::Class {
                     original,
                     obfuscated,
@@ -156,14 +314,54 @@ impl<'s> ProguardMapper<'s> {
                         original_endline,
                     });
                 }
-                _ => {}
             }
         }
         if !class.original.is_empty() {
             classes.insert(class.obfuscated, class);
         }
 
-        Self { classes }
+        Self {
+            classes,
+            source_cache: RefCell::new(HashMap::new()),
+            source: mapping.source(),
+            body_offset: body_offset.min(mapping.source().len()),
+            map_id,
+            map_hash,
+            has_map_hash,
+        }
+    }
+
+    /// The mapping's `pg_map_id`, if its header declares one.
+    pub fn map_id(&self) -> Option<&'s str> {
+        self.map_id
+    }
+
+    /// The mapping's `pg_map_id`, parsed as a [`Uuid`].
+    ///
+    /// Returns `None` when the header has no `pg_map_id`, or it isn't a
+    /// valid UUID.
+    pub fn uuid(&self) -> Option<Uuid> {
+        self.map_id.and_then(|id| Uuid::parse_str(id).ok())
+    }
+
+    /// Verifies the mapping against its declared `pg_map_hash`, if any.
+    ///
+    /// Recomputes the SHA-256 digest of the mapping body (everything below
+    /// the header) and compares it to the hash stored in the `pg_map_hash`
+    /// header line. Returns `true` when there is no declared hash to check,
+    /// since there is nothing to contradict; returns `false` on a mismatch
+    /// *or* on a `pg_map_hash` header that failed to parse, since either one
+    /// indicates a truncated or otherwise corrupted mapping file.
+    pub fn verify(&self) -> bool {
+        if !self.has_map_hash {
+            return true;
+        }
+        let Some(expected) = self.map_hash else {
+            return false;
+        };
+        let body = &self.source[self.body_offset..];
+        let actual: [u8; 32] = Sha256::digest(body.as_bytes()).into();
+        actual == expected
     }
 
     /// Remaps an obfuscated Class.
@@ -202,11 +400,122 @@ impl<'s> ProguardMapper<'s> {
         RemappedFrameIter::empty()
     }
 
+    /// Remaps a single Stackframe, resolving the surrounding source lines
+    /// for each result via `source_provider`.
+    ///
+    /// This behaves like [`remap_frame`], except that each returned frame
+    /// additionally carries up to [`CONTEXT_LINES`] lines of source before
+    /// and after the mapped line. When `source_provider` has no source for
+    /// the frame's file, or an `expected_hash` is set and does not match the
+    /// bytes it returns, the frame is still returned, just without context.
+    ///
+    /// [`remap_frame`]: Self::remap_frame
+    pub fn remap_frame_with_context(
+        &'s self,
+        frame: &StackFrame<'s>,
+        source_provider: &mut dyn SourceProvider,
+    ) -> Vec<StackFrameWithContext<'s>> {
+        self.remap_frame(frame)
+            .map(|frame| self.attach_context(frame, source_provider))
+            .collect()
+    }
+
+    /// Resolves the source context for a single already-remapped frame.
+    fn attach_context(
+        &self,
+        frame: StackFrame<'s>,
+        source_provider: &mut dyn SourceProvider,
+    ) -> StackFrameWithContext<'s> {
+        let (file, line) = match (frame.file.as_deref(), frame.line) {
+            (Some(file), Some(line)) if line > 0 => (file, line),
+            _ => return frame.into(),
+        };
+
+        let Some(lines) = self.cached_lines(file, source_provider) else {
+            return frame.into();
+        };
+
+        // `line` is 1-based; a frame pointing past the end of an otherwise
+        // perfectly fine file just has no context to show, it doesn’t mean
+        // the file itself is unusable.
+        let Some(index) = line.checked_sub(1) else {
+            return frame.into();
+        };
+        let Some(context_line) = lines.get(index) else {
+            return frame.into();
+        };
+
+        let pre_start = index.saturating_sub(CONTEXT_LINES);
+        let post_end = lines.len().min(index + 1 + CONTEXT_LINES);
+
+        StackFrameWithContext {
+            frame,
+            pre_context: lines[pre_start..index].to_vec(),
+            context_line: Some(context_line.clone()),
+            post_context: lines[index + 1..post_end].to_vec(),
+        }
+    }
+
+    /// Returns the decoded lines of `file`, fetching and hash-verifying it
+    /// through `source_provider` only on the first lookup; later calls for
+    /// the same file name, whether it resolved or not, hit the cache.
+    fn cached_lines(
+        &self,
+        file: &str,
+        source_provider: &mut dyn SourceProvider,
+    ) -> Option<Rc<Vec<String>>> {
+        if let Some(cached) = self.source_cache.borrow().get(file) {
+            return match cached {
+                CachedSource::Lines(lines) => Some(Rc::clone(lines)),
+                CachedSource::Unusable => None,
+            };
+        }
+
+        let cached = self.load_source(file, source_provider);
+        let result = match &cached {
+            CachedSource::Lines(lines) => Some(Rc::clone(lines)),
+            CachedSource::Unusable => None,
+        };
+        self.source_cache
+            .borrow_mut()
+            .insert(file.to_owned(), cached);
+        result
+    }
+
+    /// Fetches `file` from `source_provider`, verifies its hash if one is
+    /// declared, and decodes it into lines.
+    fn load_source(&self, file: &str, source_provider: &mut dyn SourceProvider) -> CachedSource {
+        let Some(source) = source_provider.get_source(file) else {
+            return CachedSource::Unusable;
+        };
+
+        if let Some(expected) = source_provider.expected_hash(file) {
+            let actual: [u8; 32] = Sha256::digest(&source).into();
+            if actual != expected {
+                return CachedSource::Unusable;
+            }
+        }
+
+        match std::str::from_utf8(&source) {
+            Ok(text) => CachedSource::Lines(Rc::new(text.lines().map(str::to_string).collect())),
+            Err(_) => CachedSource::Unusable,
+        }
+    }
+
     /// Remaps a complete Java StackTrace.
     pub fn remap_stacktrace(&'s self, input: &'s str) -> Result<String, std::fmt::Error> {
         let mut stacktrace = String::new();
-        for line in input.lines() {
+        for (i, line) in input.lines().enumerate() {
+            let is_throwable_line = i == 0
+                || THROWABLE_LINE_PREFIXES
+                    .iter()
+                    .any(|prefix| line.trim_start().starts_with(prefix));
+
             match StackFrame::try_parse(line.as_ref()) {
+                None if is_throwable_line => match self.remap_exception_header(line) {
+                    Some(remapped) => writeln!(&mut stacktrace, "{}", remapped)?,
+                    None => writeln!(&mut stacktrace, "{}", line)?,
+                },
                 None => writeln!(&mut stacktrace, "{}", line)?,
                 Some(frame) => {
                     let mut remapped = self.remap_frame(&frame).peekable();
@@ -217,11 +526,12 @@ impl<'s> ProguardMapper<'s> {
                     for line in remapped {
                         writeln!(
                             &mut stacktrace,
-                            "    at {}.{}({}:{})",
+                            "    at {}.{}({}{})",
                             line.class,
                             line.method,
                             line.file.as_deref().unwrap_or("<unknown>"),
                             line.line
+                                .map_or_else(String::new, |line| format!(":{}", line))
                         )?;
                     }
                 }
@@ -229,4 +539,231 @@ impl<'s> ProguardMapper<'s> {
         }
         Ok(stacktrace)
     }
+
+    /// Remaps the obfuscated throwable class name embedded in an exception
+    /// header or `Caused by:`/`Suppressed:` line, e.g. `a.b.C: message`.
+    ///
+    /// Returns `None` when `line` does not carry a class that is known to
+    /// this mapping, leaving it for the caller to keep the line unchanged —
+    /// this also covers `... N more` lines and plain message text, neither
+    /// of which name a class we could remap.
+    fn remap_exception_header(&'s self, line: &str) -> Option<String> {
+        let indent_len = line.len() - line.trim_start().len();
+        let (indent, rest) = line.split_at(indent_len);
+
+        let (prefix, rest) = THROWABLE_LINE_PREFIXES
+            .iter()
+            .find_map(|&prefix| rest.strip_prefix(prefix).map(|rest| (prefix, rest)))
+            .unwrap_or(("", rest));
+
+        let (class, suffix) = match rest.find(':') {
+            Some(colon) => (&rest[..colon], &rest[colon..]),
+            None => (rest, ""),
+        };
+
+        let original = self.remap_class(class.trim())?;
+        Some(format!("{}{}{}{}", indent, prefix, original, suffix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two overloads of `a.b()` collapse onto the same obfuscated name but
+    // cover disjoint original methods, disambiguated only by line range.
+    const OVERLOADED_MAPPING: &str = "\
+original.Class -> a:
+    1:2:void firstOverload():10:11 -> b
+    3:4:void secondOverload():20:21 -> b
+";
+
+    #[test]
+    fn remap_frame_with_line_picks_matching_overload() {
+        let mapper = ProguardMapper::from(OVERLOADED_MAPPING);
+        let frame = StackFrame::new("a", "b", 3);
+
+        let remapped: Vec<_> = mapper.remap_frame(&frame).collect();
+        assert_eq!(remapped.len(), 1);
+        assert_eq!(remapped[0].method(), "secondOverload");
+    }
+
+    #[test]
+    fn remap_frame_without_line_yields_single_best_effort_result() {
+        let mapper = ProguardMapper::from(OVERLOADED_MAPPING);
+        let frame = StackFrame::new("a", "b", None);
+
+        let remapped: Vec<_> = mapper.remap_frame(&frame).collect();
+        assert_eq!(remapped.len(), 1);
+        assert_eq!(remapped[0].class(), "original.Class");
+    }
+
+    struct StubProvider {
+        sources: HashMap<String, Vec<u8>>,
+        hashes: HashMap<String, [u8; 32]>,
+        calls: RefCell<usize>,
+    }
+
+    impl SourceProvider for StubProvider {
+        fn get_source(&mut self, file: &str) -> Option<Vec<u8>> {
+            *self.calls.borrow_mut() += 1;
+            self.sources.get(file).cloned()
+        }
+
+        fn expected_hash(&self, file: &str) -> Option<[u8; 32]> {
+            self.hashes.get(file).copied()
+        }
+    }
+
+    // Two obfuscated methods on the same class mapping into the same file,
+    // one at a line far past its end and one well within it.
+    const SAME_FILE_MAPPING: &str = "\
+original.Class -> a:
+    1:1:void far():100:100 -> b
+    2:2:void near():2:2 -> c
+";
+
+    fn numbered_lines(count: usize) -> Vec<u8> {
+        (1..=count)
+            .map(|n| format!("line{}\n", n))
+            .collect::<String>()
+            .into_bytes()
+    }
+
+    #[test]
+    fn remap_frame_with_context_resolves_surrounding_lines() {
+        let mapper = ProguardMapper::from(SAME_FILE_MAPPING);
+        let mut provider = StubProvider {
+            sources: HashMap::from([("Original.java".to_string(), numbered_lines(5))]),
+            hashes: HashMap::new(),
+            calls: RefCell::new(0),
+        };
+
+        let frame = StackFrame::new("a", "c", 2).with_file("Original.java");
+        let result = mapper.remap_frame_with_context(&frame, &mut provider);
+
+        assert_eq!(result[0].context_line.as_deref(), Some("line2"));
+        assert_eq!(*provider.calls.borrow(), 1);
+    }
+
+    #[test]
+    fn remap_frame_with_context_out_of_range_line_does_not_poison_file() {
+        let mapper = ProguardMapper::from(SAME_FILE_MAPPING);
+        let mut provider = StubProvider {
+            sources: HashMap::from([("Original.java".to_string(), numbered_lines(5))]),
+            hashes: HashMap::new(),
+            calls: RefCell::new(0),
+        };
+
+        // `far` remaps to line 100, past the end of the 5-line source.
+        let far = StackFrame::new("a", "b", 1).with_file("Original.java");
+        let far_result = mapper.remap_frame_with_context(&far, &mut provider);
+        assert_eq!(far_result[0].context_line, None);
+
+        // A later frame pointing at a valid line of the *same* file must
+        // still get context: the earlier per-line miss must not have marked
+        // the whole file as unusable, and the file is fetched only once.
+        let near = StackFrame::new("a", "c", 2).with_file("Original.java");
+        let near_result = mapper.remap_frame_with_context(&near, &mut provider);
+        assert_eq!(near_result[0].context_line.as_deref(), Some("line2"));
+
+        assert_eq!(*provider.calls.borrow(), 1);
+    }
+
+    #[test]
+    fn remap_frame_with_context_skips_on_hash_mismatch() {
+        let mapper = ProguardMapper::from(SAME_FILE_MAPPING);
+        let mut provider = StubProvider {
+            sources: HashMap::from([("Original.java".to_string(), numbered_lines(5))]),
+            hashes: HashMap::from([("Original.java".to_string(), [0xffu8; 32])]),
+            calls: RefCell::new(0),
+        };
+
+        let frame = StackFrame::new("a", "c", 2).with_file("Original.java");
+        let result = mapper.remap_frame_with_context(&frame, &mut provider);
+
+        assert_eq!(result[0].context_line, None);
+    }
+
+    // A later `# pg_map_id` comment sitting in the mapping body (R8 emits
+    // plenty of structured `#` comments past the header) must not override
+    // the real header value, and its hash intentionally doesn't match the
+    // body so `verify` is expected to fail.
+    const MAPPING_WITH_HEADER: &str = "\
+# pg_map_id: real-id
+# pg_map_hash: SHA-256 deadbeef00000000000000000000000000000000000000000000000000000000
+original.Class -> a:
+    1:1:void method():1:1 -> b
+# pg_map_id: evil-id-that-should-not-apply
+";
+
+    const MAPPING_WITH_MALFORMED_HASH: &str = "\
+# pg_map_hash: SHA-256 not-valid-hex
+original.Class -> a:
+    1:1:void method():1:1 -> b
+";
+
+    #[test]
+    fn header_fields_are_only_captured_from_the_leading_header() {
+        let mapper = ProguardMapper::from(MAPPING_WITH_HEADER);
+        assert_eq!(mapper.map_id(), Some("real-id"));
+    }
+
+    #[test]
+    fn mismatched_map_hash_fails_verification() {
+        let mapper = ProguardMapper::from(MAPPING_WITH_HEADER);
+        assert!(!mapper.verify());
+    }
+
+    #[test]
+    fn malformed_map_hash_fails_verification() {
+        let mapper = ProguardMapper::from(MAPPING_WITH_MALFORMED_HASH);
+        assert!(!mapper.verify());
+    }
+
+    #[test]
+    fn verify_with_no_hash_header_is_trivially_true() {
+        let mapper = ProguardMapper::from(OVERLOADED_MAPPING);
+        assert!(mapper.verify());
+    }
+
+    // CRLF-terminated mapping (as exported by some Windows/Android Studio
+    // toolchains): the header line ends in `\r\n`, and `str::lines()` would
+    // strip the `\r` too, undercounting `body_offset` by one byte if we
+    // naively summed `raw_line.len() + 1` for each header line.
+    const MAPPING_WITH_CRLF: &str = "# pg_map_hash: SHA-256 602af4c90d3c674392cf3c2026ff44cc42daee826a21fcdf2c4ba28a1da95241\r\noriginal.Class -> a:\r\n    1:1:void method():1:1 -> b\r\n";
+
+    #[test]
+    fn verify_succeeds_on_crlf_terminated_mapping() {
+        let mapper = ProguardMapper::from(MAPPING_WITH_CRLF);
+        assert!(mapper.verify());
+    }
+
+    const THROWABLE_MAPPING: &str = "\
+original.Main -> a:
+    1:1:void run():10:10 -> b
+original.Cause -> c:
+    1:1:void fail():5:5 -> d
+";
+
+    #[test]
+    fn remap_stacktrace_remaps_caused_by_and_suppressed_headers() {
+        let mapper = ProguardMapper::from(THROWABLE_MAPPING);
+        let input = "\
+a: boom
+    at a.b(Main.java:1)
+Caused by: c: oops
+    at c.d(Cause.java:1)
+Suppressed: c: also oops
+    at c.d(Cause.java:1)
+";
+
+        let remapped = mapper.remap_stacktrace(input).unwrap();
+
+        assert!(remapped.starts_with("original.Main: boom\n"));
+        assert!(remapped.contains("    at original.Main.run(Main.java:10)\n"));
+        assert!(remapped.contains("Caused by: original.Cause: oops\n"));
+        assert!(remapped.contains("Suppressed: original.Cause: also oops\n"));
+        assert!(remapped.contains("    at original.Cause.fail(Cause.java:5)\n"));
+    }
 }
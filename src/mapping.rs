@@ -0,0 +1,261 @@
+/// A line-mapping for an obfuscated method.
+///
+/// Proguard mapping files may record a line range the obfuscated method
+/// occupies (`startline`..`endline`), and the range it originally came from.
+/// The original range is left unset when a line wasn't renumbered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineMapping {
+    pub startline: usize,
+    pub endline: usize,
+    pub original_startline: Option<usize>,
+    pub original_endline: Option<usize>,
+}
+
+/// A single record of a [`ProguardMapping`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProguardRecord<'s> {
+    /// A `# key: value` header comment, as emitted before the first class.
+    Header {
+        key: &'s str,
+        value: Option<&'s str>,
+    },
+    /// A class mapping, in the form of `original -> obfuscated:`.
+    Class {
+        original: &'s str,
+        obfuscated: &'s str,
+    },
+    /// A method (or field) mapping nested below a [`Class`](Self::Class).
+    Method {
+        original: &'s str,
+        obfuscated: &'s str,
+        original_class: Option<&'s str>,
+        line_mapping: Option<LineMapping>,
+    },
+}
+
+/// An error encountered while parsing a single line of a [`ProguardMapping`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError<'s> {
+    pub line: &'s str,
+}
+
+/// A parsed view of a raw Proguard/R8 `mapping.txt` file.
+#[derive(Clone, Debug)]
+pub struct ProguardMapping<'s> {
+    source: &'s str,
+}
+
+impl<'s> ProguardMapping<'s> {
+    /// Creates a new `ProguardMapping` from raw mapping-file contents.
+    pub fn new(source: &'s str) -> Self {
+        Self { source }
+    }
+
+    /// The raw, unparsed contents this mapping was created from.
+    pub fn source(&self) -> &'s str {
+        self.source
+    }
+
+    /// Iterates over the individual records of the mapping.
+    pub fn iter(&self) -> RecordIter<'s> {
+        RecordIter {
+            lines: self.source.lines(),
+        }
+    }
+}
+
+/// Iterator over the [`ProguardRecord`]s of a [`ProguardMapping`].
+#[derive(Clone, Debug)]
+pub struct RecordIter<'s> {
+    lines: std::str::Lines<'s>,
+}
+
+impl<'s> Iterator for RecordIter<'s> {
+    type Item = Result<ProguardRecord<'s>, ParseError<'s>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.lines.next()?;
+        Some(parse_line(line))
+    }
+}
+
+fn parse_line(line: &str) -> Result<ProguardRecord<'_>, ParseError<'_>> {
+    if let Some(comment) = line.strip_prefix('#') {
+        let comment = comment.trim();
+        return Ok(match comment.split_once(':') {
+            Some((key, value)) => ProguardRecord::Header {
+                key: key.trim(),
+                value: Some(value.trim()).filter(|v| !v.is_empty()),
+            },
+            None => ProguardRecord::Header {
+                key: comment,
+                value: None,
+            },
+        });
+    }
+
+    if !line.starts_with(' ') && !line.starts_with('\t') {
+        let line = line
+            .trim_end()
+            .strip_suffix(':')
+            .ok_or(ParseError { line })?;
+        let (original, obfuscated) = line.split_once(" -> ").ok_or(ParseError { line })?;
+        return Ok(ProguardRecord::Class {
+            original,
+            obfuscated,
+        });
+    }
+
+    let trimmed = line.trim();
+    let (signature, obfuscated) = trimmed.split_once(" -> ").ok_or(ParseError { line })?;
+
+    let (line_mapping, rest) = match signature.split_once(':') {
+        Some((first, rest)) => match rest.split_once(':') {
+            Some((second, rest)) => {
+                let startline: usize = first.parse().map_err(|_| ParseError { line })?;
+                let endline: usize = second.parse().map_err(|_| ParseError { line })?;
+                (
+                    Some(LineMapping {
+                        startline,
+                        endline,
+                        original_startline: None,
+                        original_endline: None,
+                    }),
+                    rest,
+                )
+            }
+            None => return Err(ParseError { line }),
+        },
+        None => (None, signature),
+    };
+
+    // `rest` looks like `<type> <class>.<method>(<args>)` optionally followed
+    // by `:<original-startline>:<original-endline>`. A field mapping has no
+    // parens at all, e.g. `<type> <class>.<field> -> <obfuscated>`.
+    let (before_paren, after_paren) = match rest.find('(') {
+        Some(paren) => {
+            let close_paren = rest[paren..].find(')').map(|i| i + paren).unwrap_or(paren);
+            (&rest[..paren], &rest[close_paren + 1..])
+        }
+        None => (rest, ""),
+    };
+
+    let qualified = before_paren.rsplit(' ').next().ok_or(ParseError { line })?;
+    let (original_class, original) = match qualified.rsplit_once('.') {
+        Some((class, method)) => (Some(class), method),
+        None => (None, qualified),
+    };
+
+    let line_mapping = line_mapping.map(|mut line_mapping| {
+        let mut parts = after_paren.trim_start_matches(':').splitn(2, ':');
+        if let Some(start) = parts.next().and_then(|s| s.parse().ok()) {
+            line_mapping.original_startline = Some(start);
+            line_mapping.original_endline = parts.next().and_then(|s| s.parse().ok());
+        }
+        line_mapping
+    });
+
+    Ok(ProguardRecord::Method {
+        original,
+        obfuscated,
+        original_class,
+        line_mapping,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_header_comment() {
+        assert_eq!(
+            parse_line("# pg_map_id: abc"),
+            Ok(ProguardRecord::Header {
+                key: "pg_map_id",
+                value: Some("abc"),
+            })
+        );
+        assert_eq!(
+            parse_line("# common.attributes"),
+            Ok(ProguardRecord::Header {
+                key: "common.attributes",
+                value: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parses_class_mapping() {
+        assert_eq!(
+            parse_line("original.Class -> a:"),
+            Ok(ProguardRecord::Class {
+                original: "original.Class",
+                obfuscated: "a",
+            })
+        );
+    }
+
+    #[test]
+    fn parses_method_mapping_with_line_numbers() {
+        assert_eq!(
+            parse_line("    1:2:void method(int):10:11 -> b"),
+            Ok(ProguardRecord::Method {
+                original: "method",
+                obfuscated: "b",
+                original_class: None,
+                line_mapping: Some(LineMapping {
+                    startline: 1,
+                    endline: 2,
+                    original_startline: Some(10),
+                    original_endline: Some(11),
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn parses_field_mapping() {
+        assert_eq!(
+            parse_line("    int fieldName -> b"),
+            Ok(ProguardRecord::Method {
+                original: "fieldName",
+                obfuscated: "b",
+                original_class: None,
+                line_mapping: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parses_field_mapping_with_qualified_type() {
+        assert_eq!(
+            parse_line("    original.Other original.Class.fieldName -> b"),
+            Ok(ProguardRecord::Method {
+                original: "fieldName",
+                obfuscated: "b",
+                original_class: Some("original.Class"),
+                line_mapping: None,
+            })
+        );
+    }
+
+    #[test]
+    fn iterates_a_full_mapping() {
+        let mapping = ProguardMapping::new(
+            "original.Class -> a:\n    int fieldName -> b\n    1:1:void method():5:5 -> c\n",
+        );
+
+        let records: Vec<_> = mapping.iter().map(Result::unwrap).collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(
+            records[1],
+            ProguardRecord::Method {
+                original: "fieldName",
+                obfuscated: "b",
+                original_class: None,
+                line_mapping: None,
+            }
+        );
+    }
+}
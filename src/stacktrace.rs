@@ -0,0 +1,91 @@
+use std::borrow::Cow;
+
+/// A single frame of a (Java) stacktrace.
+///
+/// Can either be parsed from a raw stacktrace via [`StackFrame::try_parse`],
+/// or constructed directly to be fed into [`ProguardMapper::remap_frame`].
+///
+/// [`ProguardMapper::remap_frame`]: crate::ProguardMapper::remap_frame
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StackFrame<'s> {
+    pub(crate) class: Cow<'s, str>,
+    pub(crate) method: Cow<'s, str>,
+    pub(crate) file: Option<Cow<'s, str>>,
+    /// The line number the frame was recorded at, if any.
+    ///
+    /// Native or synthetic frames are sometimes emitted without a line
+    /// number; `remap_frame` still deobfuscates the class and method in
+    /// that case, just without resolving to a specific original line.
+    pub(crate) line: Option<usize>,
+}
+
+impl<'s> StackFrame<'s> {
+    /// Creates a new StackFrame with the given `class`, `method` and `line`.
+    pub fn new(class: &'s str, method: &'s str, line: impl Into<Option<usize>>) -> Self {
+        Self {
+            class: Cow::Borrowed(class),
+            method: Cow::Borrowed(method),
+            file: None,
+            line: line.into(),
+        }
+    }
+
+    /// Attaches the `file` this frame was recorded in.
+    pub fn with_file(mut self, file: &'s str) -> Self {
+        self.file = Some(Cow::Borrowed(file));
+        self
+    }
+
+    /// The (obfuscated, or already remapped) class name.
+    pub fn class(&self) -> &str {
+        &self.class
+    }
+
+    /// The (obfuscated, or already remapped) method name.
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// The file this frame was recorded in, if known.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// The line this frame was recorded at, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Tries to parse a [`StackFrame`] out of a single line of a raw Java
+    /// stacktrace, in the form of: `    at class.method(file:line)`.
+    ///
+    /// Returns `None` if the line does not look like a stack frame.
+    pub fn try_parse(line: &'s str) -> Option<Self> {
+        let line = line.trim_start().strip_prefix("at ")?;
+        let open_paren = line.find('(')?;
+        let close_paren = line.rfind(')')?;
+        if close_paren < open_paren {
+            return None;
+        }
+
+        let qualified_method = &line[..open_paren];
+        let location = &line[open_paren + 1..close_paren];
+
+        let dot = qualified_method.rfind('.')?;
+        let class = &qualified_method[..dot];
+        let method = &qualified_method[dot + 1..];
+
+        let (file, line_no) = match location.rsplit_once(':') {
+            Some((file, line_no)) => (Some(file), line_no.parse().ok()),
+            None if location.is_empty() => (None, None),
+            None => (Some(location), None),
+        };
+
+        Some(Self {
+            class: Cow::Borrowed(class),
+            method: Cow::Borrowed(method),
+            file: file.map(Cow::Borrowed),
+            line: line_no,
+        })
+    }
+}